@@ -0,0 +1,102 @@
+//! Background music and one-shot effects, gated behind the `audio` cargo
+//! feature so the headless trainer and CI builds can skip linking `rodio`.
+
+#[cfg(feature = "audio")]
+mod backend {
+    use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    struct Device {
+        _stream: OutputStream,
+        handle: OutputStreamHandle,
+        fire_path: String,
+        explosion_path: String,
+    }
+
+    /// `None` for a silent player (headless training games), which never
+    /// opens an output device at all rather than relying on the `audio`
+    /// feature being off.
+    pub struct AudioPlayer {
+        device: Option<Device>,
+    }
+    impl AudioPlayer {
+        /// Opens the default output device, starts `background_path` looping,
+        /// and remembers the effect paths for `play_fire`/`play_explosion`.
+        pub fn new(background_path: &str, fire_path: &str, explosion_path: &str, volume: f32) -> Self {
+            let (stream, handle) =
+                OutputStream::try_default().expect("Failed to open audio output device.");
+            let device = Device {
+                _stream: stream,
+                handle,
+                fire_path: fire_path.to_string(),
+                explosion_path: explosion_path.to_string(),
+            };
+            device.play_looping(background_path, volume);
+            Self {
+                device: Some(device),
+            }
+        }
+
+        pub fn silent() -> Self {
+            Self { device: None }
+        }
+
+        pub fn play_fire(&self) {
+            if let Some(device) = &self.device {
+                device.play_once(&device.fire_path.clone());
+            }
+        }
+
+        pub fn play_explosion(&self) {
+            if let Some(device) = &self.device {
+                device.play_once(&device.explosion_path.clone());
+            }
+        }
+    }
+
+    impl Device {
+        fn play_looping(&self, path: &str, volume: f32) {
+            let Ok(file) = File::open(path) else { return };
+            let Ok(source) = Decoder::new(BufReader::new(file)) else {
+                return;
+            };
+            if let Ok(sink) = Sink::try_new(&self.handle) {
+                sink.set_volume(volume);
+                sink.append(source.repeat_infinite());
+                sink.detach();
+            }
+        }
+
+        fn play_once(&self, path: &str) {
+            let Ok(file) = File::open(path) else { return };
+            let Ok(source) = Decoder::new(BufReader::new(file)) else {
+                return;
+            };
+            if let Ok(sink) = Sink::try_new(&self.handle) {
+                sink.append(source);
+                sink.detach();
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "audio"))]
+mod backend {
+    pub struct AudioPlayer;
+    impl AudioPlayer {
+        pub fn new(_background_path: &str, _fire_path: &str, _explosion_path: &str, _volume: f32) -> Self {
+            Self
+        }
+
+        pub fn silent() -> Self {
+            Self
+        }
+
+        pub fn play_fire(&self) {}
+
+        pub fn play_explosion(&self) {}
+    }
+}
+
+pub use backend::AudioPlayer;
@@ -0,0 +1,61 @@
+pub(crate) const FRAC_BITS: u32 = 9;
+pub(crate) const ONE: i32 = 1 << FRAC_BITS;
+
+/// How much the scroll accumulator advances per tick. Anything less than
+/// `ONE` means several ticks pass between whole-row changes, which is what
+/// makes the fractional bits meaningful instead of always landing on zero.
+pub(crate) const SCROLL_STEP: i32 = ONE / 4;
+
+/// Reduces a fixed-point scroll accumulator (the `Game::scroll` shared with
+/// the frame-counter thread) down to the whole scene row it lands on.
+pub(crate) fn row_from_scroll(scroll: i32) -> usize {
+    (scroll >> FRAC_BITS).max(0) as usize
+}
+
+const FADE_GLYPHS: [&str; 4] = ["▓", "▒", "░", " "];
+
+/// A glyph for the bottom screen row that fades as the scroll accumulator's
+/// fractional bits climb toward the next whole row, so the row visibly
+/// scrolls out instead of snapping away all at once. `None` once the
+/// accumulator lands back on a whole row.
+pub(crate) fn exit_fade_glyph(scroll: i32) -> Option<&'static str> {
+    let fraction = scroll & (ONE - 1);
+    if fraction == 0 {
+        return None;
+    }
+    let bucket = (fraction * FADE_GLYPHS.len() as i32 / ONE) as usize;
+    Some(FADE_GLYPHS[bucket.min(FADE_GLYPHS.len() - 1)])
+}
+
+/// The horizontal half of the fixed-point camera, ported from
+/// doukutsu-rs's `Frame`: the centering offset is stored as `value * ONE`
+/// (9 fractional bits) and only has to be recomputed when the terminal is
+/// resized, unlike the vertical scroll which advances every tick.
+pub(crate) struct Camera {
+    center_offset: i32,
+}
+impl Camera {
+    pub fn new() -> Self {
+        Self { center_offset: 0 }
+    }
+
+    /// Recomputes the horizontal centering offset for a scene designed at
+    /// `designed_width` columns now being rendered into a `terminal_width`
+    /// terminal, mirroring doukutsu-rs's `immediate_update` centering math:
+    /// split the slack evenly so the playfield sits in the middle instead
+    /// of hugging the left edge.
+    pub fn recenter(&mut self, terminal_width: u16, designed_width: u16) {
+        self.center_offset = if terminal_width > designed_width {
+            i32::from(terminal_width - designed_width) / 2 * ONE
+        } else {
+            0
+        };
+    }
+
+    /// Converts a signed world column to the screen column it should
+    /// render at; callers pass the result straight to `FrameBuffer::set`,
+    /// which drops anything that lands off either edge.
+    pub fn to_screen_x(&self, world_x: i32) -> i32 {
+        world_x + (self.center_offset >> FRAC_BITS)
+    }
+}
@@ -0,0 +1,68 @@
+use crossterm::style::Color;
+
+use crate::camera::Camera;
+use crate::{FrameBuffer, RenderCell};
+
+/// Per-frame context entities need while ticking. Nothing here is specific
+/// to missiles, so new kinds (enemies, fuel depots, bridges) can read the
+/// same state instead of growing their own plumbing. Missiles don't need
+/// `frame`/`rows`/`cols` yet, but future entity kinds will.
+#[allow(dead_code)]
+pub(crate) struct GameState {
+    pub frame: usize,
+    pub rows: u16,
+    pub cols: u16,
+}
+
+/// Something that moves and draws itself every frame. Missiles are the
+/// first case; future scene objects (helicopters, fuel depots) implement
+/// this instead of getting their own bespoke handling in `Game`. Positions
+/// are signed world columns; `draw` runs them through `camera` so an entity
+/// that's partly off the left edge still renders correctly.
+pub(crate) trait GameEntity: Send {
+    fn tick(&mut self, state: &GameState);
+    fn draw(&self, buffer: &mut FrameBuffer, camera: &Camera);
+    fn position(&self) -> (i32, u16);
+    fn is_alive(&self) -> bool;
+    fn kill(&mut self);
+}
+
+pub(crate) struct Missile {
+    x: i32,
+    y: u16,
+    alive: bool,
+}
+impl Missile {
+    pub fn new(x: i32, y: u16) -> Self {
+        Self { x, y, alive: true }
+    }
+}
+impl GameEntity for Missile {
+    fn tick(&mut self, _state: &GameState) {
+        if self.y == 0 {
+            self.alive = false;
+        } else {
+            self.y -= 1;
+        }
+    }
+
+    fn draw(&self, buffer: &mut FrameBuffer, camera: &Camera) {
+        buffer.set(
+            camera.to_screen_x(self.x),
+            self.y,
+            RenderCell::new("ðŸ\u{AD}¯", Color::Red, Color::Blue),
+        );
+    }
+
+    fn position(&self) -> (i32, u16) {
+        (self.x, self.y)
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    fn kill(&mut self) {
+        self.alive = false;
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const MAX_ENTRIES: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+}
+
+/// The persistent top-`MAX_ENTRIES` list `render_gameover` displays, kept
+/// sorted highest first. Missing or unparsable on first launch, same as
+/// `Settings`, since there's nothing to recover from an empty table.
+#[derive(Serialize, Deserialize, Default)]
+pub struct HighScoreTable {
+    entries: Vec<HighScore>,
+}
+impl HighScoreTable {
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) {
+        let contents = toml::to_string_pretty(self).expect("Failed to serialize high scores.");
+        let _ = fs::write(path, contents);
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// Whether `score` would make the table, either because there's still
+    /// room or because it beats the current lowest entry. A score of 0
+    /// (dying on frame one) never qualifies, so an empty table doesn't
+    /// prompt every first-time player for initials.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_ENTRIES
+                || self.entries.last().is_some_and(|worst| score > worst.score))
+    }
+
+    /// Records `score` under `initials`, re-sorts, and trims back down to
+    /// `MAX_ENTRIES`. Callers should check `qualifies` first if they only
+    /// want to prompt for initials on an actual new high score.
+    pub fn insert(&mut self, initials: String, score: u32) {
+        self.entries.push(HighScore { initials, score });
+        self.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        self.entries.truncate(MAX_ENTRIES);
+    }
+}
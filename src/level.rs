@@ -0,0 +1,92 @@
+use crossterm::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+use crate::Kind;
+
+/// An authorable level: a display name, the width the river bands were
+/// designed at, a render theme per `Kind`, and the segment templates
+/// `Scene::make` stitches end-to-end to build the map.
+#[derive(Deserialize)]
+pub struct LevelConfig {
+    pub name: String,
+    #[serde(default = "default_width")]
+    pub width: u16,
+    pub theme: HashMap<Kind, ThemeColors>,
+    pub segments: Vec<SegmentTemplate>,
+}
+impl LevelConfig {
+    pub fn load(path: &str) -> Self {
+        let contents =
+            fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read level file {path}."));
+        toml::from_str(&contents).expect("Failed to parse level file.")
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ThemeColors {
+    fg: String,
+    bg: String,
+}
+impl ThemeColors {
+    pub fn fg(&self) -> Color {
+        parse_color(&self.fg)
+    }
+
+    pub fn bg(&self) -> Color {
+        parse_color(&self.bg)
+    }
+}
+
+fn default_width() -> u16 {
+    80
+}
+
+fn parse_color(name: &str) -> Color {
+    match name {
+        "Black" => Color::Black,
+        "Red" => Color::Red,
+        "Green" => Color::Green,
+        "Yellow" => Color::Yellow,
+        "Blue" => Color::Blue,
+        "Magenta" => Color::Magenta,
+        "Cyan" => Color::Cyan,
+        "White" => Color::White,
+        _ => Color::Reset,
+    }
+}
+
+/// One row template: the five land/river band widths (as percentages of the
+/// terminal width), how many rows tall the band is, and what may spawn in
+/// its river cells.
+#[derive(Deserialize)]
+pub struct SegmentTemplate {
+    pub land_one: f64,
+    pub river_one: f64,
+    pub land_two: f64,
+    pub river_two: f64,
+    pub land_three: f64,
+    pub height: usize,
+    #[serde(default)]
+    pub spawns: Vec<SpawnRule>,
+}
+impl SegmentTemplate {
+    pub fn band_percentages(&self) -> [f64; 5] {
+        [
+            self.land_one,
+            self.river_one,
+            self.land_two,
+            self.river_two,
+            self.land_three,
+        ]
+    }
+}
+
+/// A chance, rolled independently per row, to drop `kind` onto a random
+/// river cell of that row.
+#[derive(Deserialize, Clone)]
+pub struct SpawnRule {
+    pub kind: Kind,
+    pub probability: f64,
+}
@@ -9,98 +9,362 @@ use crossterm::{
     ExecutableCommand, QueueableCommand,
 };
 use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Deserialize;
 use std::{
-    fs,
+    collections::HashMap,
+    env,
     io::{self, stdout, Write},
     sync::{Arc, Mutex},
     thread::{self, sleep, JoinHandle},
     time, vec,
 };
 
+mod audio;
+mod camera;
+mod entity;
+mod highscore;
+mod level;
+mod netplay;
+mod nn;
+mod settings;
+mod trainer;
+
+use camera::Camera;
+use entity::{GameEntity, GameState, Missile};
+use netplay::NetplaySession;
+use settings::Settings;
+
+pub(crate) const AUTOPILOT_WEIGHTS_PATH: &str = "autopilot.weights";
+pub(crate) const LEVEL_CONFIG_PATH: &str = "levels/classic.toml";
+pub(crate) const SETTINGS_PATH: &str = "settings.toml";
+pub(crate) const HIGHSCORES_PATH: &str = "highscores.toml";
+
+/// Points awarded per enemy destroyed; plain survival is worth one point a
+/// frame, mirroring `Fitness::score`'s weighting of the two signals.
+const ENEMY_SCORE_VALUE: u32 = 100;
+
 fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().collect();
+
+    if let Some(generations) = parse_train_flag(&args) {
+        trainer::train(generations);
+        return Ok(());
+    }
+
+    let settings = Settings::load(SETTINGS_PATH);
+
+    let autopilot = if args.iter().any(|arg| arg == "--autopilot") || settings.autopilot {
+        Some(nn::Network::load(AUTOPILOT_WEIGHTS_PATH).expect("Failed to load autopilot weights."))
+    } else {
+        None
+    };
+
+    let net = if let Some(port) = parse_value_flag(&args, "--host").and_then(|value| value.parse().ok()) {
+        Some(NetplaySession::host(port))
+    } else {
+        parse_value_flag(&args, "--join").map(|addr| NetplaySession::join(&addr))
+    };
+
     let mut stdout = stdout();
 
     enable_raw_mode()?;
     stdout.execute(EnterAlternateScreen)?.execute(Hide)?;
 
-    let mut game = Game::new();
+    let mut game = match net {
+        Some(net) => Game::with_seed(net.seed, Some(net), settings),
+        None => Game::new(settings),
+    };
+    game.autopilot = autopilot;
     game.run()?;
 
     stdout.execute(LeaveAlternateScreen)?.execute(Show)?;
     disable_raw_mode()?;
+    game.settings.save(SETTINGS_PATH);
     Ok(())
 }
 
+/// Looks for `--train [generations]`, defaulting to 100 generations when the
+/// count is omitted or isn't a number.
+fn parse_train_flag(args: &[String]) -> Option<usize> {
+    let index = args.iter().position(|arg| arg == "--train")?;
+    Some(
+        args.get(index + 1)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(100),
+    )
+}
+
+/// Looks for `name` among `args` and returns the value that follows it, e.g.
+/// `parse_value_flag(args, "--join")` for `--join 127.0.0.1:9000`.
+fn parse_value_flag(args: &[String], name: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1).cloned()
+}
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 enum State {
     Main,
     Playing,
     Paused,
+    EnterInitials,
     GameOver,
     Quit,
 }
 impl std::fmt::Display for State {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            State::Playing => write!(f, "{}", "Playing"),
-            State::GameOver => write!(f, "{}", "GameOver"),
-            State::Main => write!(f, "{}", "Main"),
-            State::Paused => write!(f, "{}", "Paused"),
-            State::Quit => write!(f, "{}", "Quit"),
+            State::Playing => write!(f, "Playing"),
+            State::EnterInitials => write!(f, "EnterInitials"),
+            State::GameOver => write!(f, "GameOver"),
+            State::Main => write!(f, "Main"),
+            State::Paused => write!(f, "Paused"),
+            State::Quit => write!(f, "Quit"),
         }
     }
 }
 
 struct Player {
-    x: u16,
+    x: i32,
     y: u16,
 }
 impl Player {
-    fn new(x: u16, y: u16) -> Self {
+    fn new(x: i32, y: u16) -> Self {
         Self { x, y }
     }
 }
 
-struct Missile {
-    x: u16,
-    y: Arc<Mutex<u16>>,
-}
-impl Missile {
-    fn new(x: u16, y: u16) -> Self {
-        Self {
-            x,
-            y: Arc::new(Mutex::new(y)),
-        }
-    }
-
-    fn fire(&mut self) {
-        let y_clone = self.y.clone();
-        thread::spawn(move || loop {
-            sleep(time::Duration::from_millis(20));
-            let mut y = y_clone.lock().unwrap();
-            if *y <= 0 {
-                break;
-            }
-            *y -= 1;
-        });
-    }
-}
-
 struct Game {
     scene: Scene,
     state: Arc<Mutex<State>>,
     frame: Arc<Mutex<usize>>,
+    scroll: Arc<Mutex<i32>>,
+    camera: Camera,
     player: Arc<Mutex<Player>>,
-    missiles: Arc<Mutex<Vec<Missile>>>,
+    entities: Arc<Mutex<Vec<Box<dyn GameEntity>>>>,
+    buffer_a: FrameBuffer,
+    buffer_b: FrameBuffer,
+    front_is_a: bool,
+    term_size: (u16, u16),
+    autopilot: Option<nn::Network>,
+    net: Option<NetplaySession>,
+    remote_player: Arc<Mutex<Player>>,
+    local_action: Arc<Mutex<Option<nn::Action>>>,
+    settings: Settings,
+    high_scores: highscore::HighScoreTable,
+    audio: audio::AudioPlayer,
+    score: u32,
+    entering_initials: Arc<Mutex<String>>,
+    initials_confirmed: Arc<Mutex<bool>>,
 }
 impl Game {
-    fn new() -> Self {
+    fn new(settings: Settings) -> Self {
+        let level_path = settings.level.clone();
+        let audio = Self::audio_for(&settings);
+        Self::build(Scene::make(&level_path, &mut thread_rng()), None, settings, audio)
+    }
+
+    /// Builds a `Game` whose scene is generated from a seed shared with a
+    /// netplay peer, so both sides' `Scene::make` produce identical rivers.
+    fn with_seed(seed: u64, net: Option<NetplaySession>, settings: Settings) -> Self {
+        let level_path = settings.level.clone();
+        let audio = Self::audio_for(&settings);
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::build(Scene::make(&level_path, &mut rng), net, settings, audio)
+    }
+
+    fn audio_for(settings: &Settings) -> audio::AudioPlayer {
+        audio::AudioPlayer::new(
+            &settings.soundtrack.background,
+            &settings.soundtrack.fire,
+            &settings.soundtrack.explosion,
+            settings.volume,
+        )
+    }
+
+    fn build(scene: Scene, net: Option<NetplaySession>, settings: Settings, audio: audio::AudioPlayer) -> Self {
+        let (cols, rows) = size().expect("Failed to get terminal size.");
         Self {
-            scene: Scene::make(),
+            scene,
             state: Arc::new(Mutex::new(State::Main)),
             frame: Arc::new(Mutex::new(0)),
+            scroll: Arc::new(Mutex::new(0)),
+            camera: Camera::new(),
             player: Arc::new(Mutex::new(Player::new(0, 0))),
-            missiles: Arc::new(Mutex::new(vec![])),
+            entities: Arc::new(Mutex::new(vec![])),
+            buffer_a: FrameBuffer::new(cols, rows),
+            buffer_b: FrameBuffer::new(cols, rows),
+            front_is_a: true,
+            term_size: (cols, rows),
+            autopilot: None,
+            net,
+            remote_player: Arc::new(Mutex::new(Player::new(0, 0))),
+            local_action: Arc::new(Mutex::new(None)),
+            settings,
+            high_scores: highscore::HighScoreTable::load(HIGHSCORES_PATH),
+            audio,
+            score: 0,
+            entering_initials: Arc::new(Mutex::new(String::new())),
+            initials_confirmed: Arc::new(Mutex::new(false)),
+        }
+    }
+
+    /// Builds a `Game` that is already `Playing` and never spawns the input
+    /// or frame-counter threads, for the genetic trainer's headless games.
+    /// Takes an explicit `seed` (rather than `Self::new`'s `thread_rng`) so
+    /// every network in a generation is scored against the same river and
+    /// fitness values stay comparable. Builds a silent `AudioPlayer` instead
+    /// of `Self::audio_for` so training doesn't open a real output device
+    /// (and start looping background music) for every headless game.
+    fn new_headless(cols: u16, rows: u16, seed: u64) -> Self {
+        let settings = Settings::default();
+        let level_path = settings.level.clone();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Self::build(
+            Scene::make(&level_path, &mut rng),
+            None,
+            settings,
+            audio::AudioPlayer::silent(),
+        );
+        game.term_size = (cols, rows);
+        game.buffer_a = FrameBuffer::new(cols, rows);
+        game.buffer_b = FrameBuffer::new(cols, rows);
+        *game.state.lock().unwrap() = State::Playing;
+
+        let mut player = game.player.lock().unwrap();
+        player.x = i32::from(cols / 2);
+        player.y = rows - 1;
+        drop(player);
+
+        game
+    }
+
+    /// Extracts the normalized features the autopilot network reads: the
+    /// land/river boundary offsets either side of the player, the distance
+    /// to the nearest enemy in the few columns around the player, and the
+    /// player's normalized x position.
+    fn extract_features(&mut self, rows: u16, cols: u16) -> [f64; nn::INPUT_SIZE] {
+        const ENEMY_LOOKAHEAD_ROWS: usize = 10;
+        const ENEMY_WINDOW: i32 = 2;
+
+        let row = camera::row_from_scroll(*self.scroll.lock().unwrap());
+        let player = self.player.lock().unwrap();
+        let mut scene = self.scene.get_current_scene(row, rows);
+        scene.reverse();
+
+        let player_x = usize::try_from(player.x).unwrap_or(0);
+        let player_row = scene
+            .get(usize::from(player.y))
+            .expect("Player row is always within the scene.");
+
+        let left_offset = (0..player_x)
+            .rev()
+            .take_while(|&col| player_row.get(col).is_some_and(|cell| cell.kind == Kind::RIVER))
+            .count() as f64;
+        let right_offset = (player_x + 1..player_row.len())
+            .take_while(|&col| player_row.get(col).is_some_and(|cell| cell.kind == Kind::RIVER))
+            .count() as f64;
+
+        let mut enemy_distances = [1.0_f64; 5];
+        for (offset_index, column_offset) in (-ENEMY_WINDOW..=ENEMY_WINDOW).enumerate() {
+            let column = player.x + column_offset;
+            if column < 0 || column >= i32::from(cols) {
+                continue;
+            }
+            let column = column as usize;
+
+            for row_distance in 1..=ENEMY_LOOKAHEAD_ROWS {
+                if row_distance > usize::from(player.y) {
+                    break;
+                }
+                let row_index = usize::from(player.y) - row_distance;
+                if scene
+                    .get(row_index)
+                    .and_then(|row| row.get(column))
+                    .is_some_and(|cell| cell.kind == Kind::ENEMY)
+                {
+                    enemy_distances[offset_index] = row_distance as f64 / ENEMY_LOOKAHEAD_ROWS as f64;
+                    break;
+                }
+            }
+        }
+
+        let player_x_normalized = f64::from(player.x) / f64::from(cols.max(1));
+
+        [
+            left_offset / f64::from(cols),
+            right_offset / f64::from(cols),
+            enemy_distances[0],
+            enemy_distances[1],
+            enemy_distances[2],
+            enemy_distances[3],
+            enemy_distances[4],
+            player_x_normalized,
+        ]
+    }
+
+    /// Applies an autopilot decision through the same player/missile state
+    /// the keyboard handler in `listen_events` drives.
+    fn apply_action(&mut self, action: nn::Action) {
+        let (cols, _) = self.term_size;
+        match action {
+            nn::Action::MoveLeft => {
+                let mut player = self.player.lock().unwrap();
+                if player.x > 0 {
+                    player.x -= 1;
+                }
+            }
+            nn::Action::MoveRight => {
+                let mut player = self.player.lock().unwrap();
+                if player.x + 1 < i32::from(cols) {
+                    player.x += 1;
+                }
+            }
+            nn::Action::Fire => {
+                let (x, y) = {
+                    let player = self.player.lock().unwrap();
+                    (player.x, player.y.saturating_sub(1))
+                };
+                self.entities
+                    .lock()
+                    .unwrap()
+                    .push(Box::new(Missile::new(x, y)));
+                self.audio.play_fire();
+            }
+            nn::Action::Idle => {}
+        }
+    }
+
+    /// Mirrors `apply_action` for the netplay peer's plane, which shares the
+    /// same entity list (so a missile either player fires can kill an
+    /// enemy) but moves its own `remote_player` instead of `self.player`.
+    fn apply_remote_action(&mut self, action: nn::Action) {
+        let (cols, _) = self.term_size;
+        match action {
+            nn::Action::MoveLeft => {
+                let mut remote_player = self.remote_player.lock().unwrap();
+                if remote_player.x > 0 {
+                    remote_player.x -= 1;
+                }
+            }
+            nn::Action::MoveRight => {
+                let mut remote_player = self.remote_player.lock().unwrap();
+                if remote_player.x + 1 < i32::from(cols) {
+                    remote_player.x += 1;
+                }
+            }
+            nn::Action::Fire => {
+                let (x, y) = {
+                    let remote_player = self.remote_player.lock().unwrap();
+                    (remote_player.x, remote_player.y.saturating_sub(1))
+                };
+                self.entities
+                    .lock()
+                    .unwrap()
+                    .push(Box::new(Missile::new(x, y)));
+                self.audio.play_fire();
+            }
+            nn::Action::Idle => {}
         }
     }
 
@@ -123,6 +387,10 @@ impl Game {
                     sleep(time::Duration::from_millis(100));
                     self.render_playing()?;
                 }
+                State::EnterInitials => {
+                    sleep(time::Duration::from_millis(100));
+                    self.render_enter_initials()?;
+                }
                 State::GameOver => {
                     sleep(time::Duration::from_millis(100));
                     self.render_gameover()?;
@@ -130,7 +398,6 @@ impl Game {
                 State::Quit => {
                     break;
                 }
-                _ => {}
             }
         }
 
@@ -139,9 +406,15 @@ impl Game {
         Ok(())
     }
 
+    /// Advances `frame`/`scroll` once per tick, unless this is a netplay
+    /// session and the peer hasn't acknowledged the current frame yet — that
+    /// gate is what keeps the two sides in lockstep instead of ticking the
+    /// scene independently and drifting apart.
     fn start_frame_counter(&mut self) -> JoinHandle<()> {
         let frame_clone = self.frame.clone();
+        let scroll_clone = self.scroll.clone();
         let state_clone = self.state.clone();
+        let remote_frame = self.net.as_ref().map(NetplaySession::remote_frame_handle);
         let join_handle = thread::spawn(move || loop {
             // sleep(time::Duration::from_millis(u64::from(BASE_SPEED_DELAY_IN_MILLIS / u64::from(*speed_rate.lock().unwrap()))));
             sleep(time::Duration::from_millis(100));
@@ -151,7 +424,15 @@ impl Game {
             }
             if *state == State::Playing {
                 let mut frame = frame_clone.lock().unwrap();
-                *frame += 1;
+                let peer_caught_up = match &remote_frame {
+                    Some(remote_frame) => *remote_frame.lock().unwrap() >= *frame as u64,
+                    None => true,
+                };
+                if peer_caught_up {
+                    *frame += 1;
+                    let mut scroll = scroll_clone.lock().unwrap();
+                    *scroll += camera::SCROLL_STEP;
+                }
             }
         });
         join_handle
@@ -159,9 +440,13 @@ impl Game {
 
     fn listen_events(&self) -> JoinHandle<()> {
         let duration = time::Duration::from_millis(250);
+        let spawn_x = self.scene.spawn_x();
         let state = self.state.clone();
         let player = self.player.clone();
-        let missiles_clone = self.missiles.clone();
+        let remote_player = self.remote_player.clone();
+        let local_action = self.local_action.clone();
+        let entering_initials = self.entering_initials.clone();
+        let initials_confirmed = self.initials_confirmed.clone();
         let join_handle = thread::spawn(move || loop {
             if poll(duration).expect("Failed to poll event.") {
                 let event = read().expect("Failed to read event.");
@@ -180,10 +465,10 @@ impl Game {
                         code: KeyCode::Char('p'),
                         kind: KeyEventKind::Press,
                         ..
-                    }) => {
+                    }) if *state != State::EnterInitials => {
                         if *state == State::Playing {
                           *state = State::Paused;
-                        } else if (*state == State::Paused) {
+                        } else if *state == State::Paused {
                             *state = State::Playing;
                         }
                     }
@@ -191,11 +476,13 @@ impl Game {
                         kind: KeyEventKind::Press,
                         ..
                     }) if *state == State::Main => {
-                        let (cols, rows) = size().expect("Failed to get terminal size.");
-                        let middle = cols / 2;
+                        let (_, rows) = size().expect("Failed to get terminal size.");
                         let mut player = player.lock().unwrap();
-                        player.x = middle;
+                        player.x = spawn_x;
                         player.y = rows - 1;
+                        let mut remote_player = remote_player.lock().unwrap();
+                        remote_player.x = spawn_x.saturating_sub(4);
+                        remote_player.y = rows - 1;
                         *state = State::Playing;
                     }
                     Event::Key(KeyEvent {
@@ -203,29 +490,45 @@ impl Game {
                         kind: KeyEventKind::Press,
                         ..
                     }) if *state == State::Playing => {
-                        let mut player = player.lock().unwrap();
-                        player.x -= 1;
+                        *local_action.lock().unwrap() = Some(nn::Action::MoveLeft);
                     }
                     Event::Key(KeyEvent {
                         code: KeyCode::Right,
                         kind: KeyEventKind::Press,
                         ..
                     }) if *state == State::Playing => {
-                        let mut player = player.lock().unwrap();
-                        player.x += 1;
+                        *local_action.lock().unwrap() = Some(nn::Action::MoveRight);
                     }
                     Event::Key(KeyEvent {
                         code: KeyCode::Char(' '),
                         kind: KeyEventKind::Press,
                         ..
                     }) if *state == State::Playing => {
-                        let player = player.lock().unwrap();
-                        let x = player.x;
-                        let y = player.y - 1;
-                        let mut missile = Missile::new(x, y);
-                        missile.fire();
-                        let missiles = &mut missiles_clone.lock().unwrap();
-                        missiles.push(missile);
+                        *local_action.lock().unwrap() = Some(nn::Action::Fire);
+                    }
+                    Event::Key(KeyEvent {
+                        code: KeyCode::Char(c),
+                        kind: KeyEventKind::Press,
+                        ..
+                    }) if *state == State::EnterInitials => {
+                        let mut initials = entering_initials.lock().unwrap();
+                        if initials.len() < 3 && c.is_ascii_alphabetic() {
+                            initials.push(c.to_ascii_uppercase());
+                        }
+                    }
+                    Event::Key(KeyEvent {
+                        code: KeyCode::Backspace,
+                        kind: KeyEventKind::Press,
+                        ..
+                    }) if *state == State::EnterInitials => {
+                        entering_initials.lock().unwrap().pop();
+                    }
+                    Event::Key(KeyEvent {
+                        code: KeyCode::Enter,
+                        kind: KeyEventKind::Press,
+                        ..
+                    }) if *state == State::EnterInitials => {
+                        *initials_confirmed.lock().unwrap() = true;
                     }
                     _ => {}
                 }
@@ -238,8 +541,9 @@ impl Game {
         let mut stdout = stdout();
 
         let s = format!(
-            "{}\r\n\r\n{}\r\n\r\n\r\n\r\n{}",
+            "{}\r\nLevel: {}\r\n\r\n{}\r\n\r\n\r\n\r\n{}",
             "River Raid Game".yellow(),
+            self.scene.name,
             "Help: (ctrl+c) Exit   (p) Pause",
             "Press any key to start..."
         );
@@ -272,9 +576,16 @@ impl Game {
     fn render_gameover(&self) -> io::Result<()> {
         let mut stdout = stdout();
 
+        let mut scores = "High Scores:\r\n".to_string();
+        for entry in self.high_scores.entries() {
+            scores.push_str(&format!("{}  {}\r\n", entry.initials, entry.score));
+        }
+
         let s = format!(
-            "{}\r\n\r\n{}",
+            "{}\r\n\r\nScore: {}\r\n\r\n{}\r\n{}",
             "Game Over!".red(),
+            self.score,
+            scores,
             "Press ctrl+c to exit.."
         );
 
@@ -289,150 +600,462 @@ impl Game {
         Ok(())
     }
 
+    /// Prompts for initials the first time this is called after a run beats
+    /// a high score entry, then commits the table once `listen_events`
+    /// flips `initials_confirmed` on Enter.
+    fn render_enter_initials(&mut self) -> io::Result<()> {
+        if *self.initials_confirmed.lock().unwrap() {
+            let initials = self.entering_initials.lock().unwrap().clone();
+            let initials = if initials.is_empty() {
+                "???".to_string()
+            } else {
+                initials
+            };
+            self.high_scores.insert(initials, self.score);
+            self.high_scores.save(HIGHSCORES_PATH);
+            self.entering_initials.lock().unwrap().clear();
+            *self.initials_confirmed.lock().unwrap() = false;
+            *self.state.lock().unwrap() = State::GameOver;
+            return Ok(());
+        }
+
+        let mut stdout = stdout();
+        let initials = self.entering_initials.lock().unwrap().clone();
+        let s = format!(
+            "{}\r\n\r\nScore: {}\r\n\r\nEnter your initials: {}_\r\n\r\n{}",
+            "New High Score!".yellow(),
+            self.score,
+            initials,
+            "(letters, backspace, enter to confirm)"
+        );
+
+        stdout
+            .queue(Clear(ClearType::All))?
+            .queue(MoveTo(0, 0))?
+            .queue(Print(s))?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+
 
     fn render_playing(&mut self) -> io::Result<()> {
         let mut stdout = stdout();
-        let (_, rows) = size()?;
+        let (cols, rows) = size()?;
+
+        if (cols, rows) != self.term_size {
+            self.resize_buffers(cols, rows)?;
+        }
+
+        if let Some(network) = self.autopilot.clone() {
+            let features = self.extract_features(rows, cols);
+            let action = network.decide(&features);
+            self.apply_action(action);
+        }
+
+        let local_action = self.local_action.lock().unwrap().take();
+        if let Some(net) = &self.net {
+            // Send every frame, not just on a keypress: the peer's frame
+            // counter gate waits for this frame's input to arrive, so two
+            // idle players would otherwise never advance past frame one.
+            let action = local_action.unwrap_or(nn::Action::Idle);
+            net.send_local(*self.frame.lock().unwrap() as u64, action);
+        }
+        if let Some(action) = local_action {
+            self.apply_action(action);
+        }
+
+        // Drain every queued input in send order rather than trusting a
+        // single latest-value slot, so a dropped poll can't skip a frame's
+        // action and desync the two sides' entities/score.
+        let mut remote_actions = vec![];
+        if let Some(net) = &self.net {
+            while let Some((_, remote_action)) = net.take_remote_input() {
+                remote_actions.push(remote_action);
+            }
+        }
+        for remote_action in remote_actions {
+            self.apply_remote_action(remote_action);
+        }
+
+        let frame_index = *self.frame.lock().unwrap();
+        let scroll_value = *self.scroll.lock().unwrap();
+        let row = camera::row_from_scroll(scroll_value);
+        let exit_fade = camera::exit_fade_glyph(scroll_value);
+        let game_state = GameState {
+            frame: frame_index,
+            rows,
+            cols,
+        };
 
-        let frame_index = self.frame.lock().unwrap();
+        self.camera.recenter(cols, self.scene.designed_width);
+        let theme = self.scene.theme.clone();
 
-        let mut scene = self.scene.get_current_scene(*frame_index, rows);
+        let mut scene = self.scene.get_current_scene(row, rows);
         scene.reverse();
 
-        for (row_index, line) in scene.iter_mut().enumerate() {
+        let outcome = Self::resolve_frame(&self.entities, &self.player, &self.audio, &mut scene, &game_state);
+
+        let back_buffer = if self.front_is_a {
+            &mut self.buffer_b
+        } else {
+            &mut self.buffer_a
+        };
+        back_buffer.clear();
+
+        let exiting_row_index = scene.len().saturating_sub(1);
+        for (row_index, line) in scene.iter().enumerate() {
             let y = row_index
                 .try_into()
                 .expect("Failed to conver usize to u16.");
-            for (col_index, cell) in line.iter_mut().enumerate() {
-                let x = col_index
-                    .try_into()
-                    .expect("Failed to convert usize to u16.");
-                stdout.queue(MoveTo(x, y))?;
-                match cell.kind {
-                    Kind::LAND => {
-                        stdout
-                            .queue(SetForegroundColor(Color::Green))?
-                            .queue(Print("â–ˆ"))?;
-                    }
-                    Kind::RIVER => {
-                        stdout
-                            .queue(SetForegroundColor(Color::Blue))?
-                            .queue(Print("â–ˆ"))?;
-                    }
-                    Kind::ENEMY => {
-                        stdout
-                            .queue(SetForegroundColor(Color::White))?
-                            .queue(SetBackgroundColor(Color::Blue))?
-                            .queue(Print("âœˆ"))?;
-                    }
-                    _ => {}
-                }
+            // the bottom row is the one about to scroll fully out of view;
+            // fade it with `exit_fade` so the transition isn't a hard jump
+            let row_fade = if row_index == exiting_row_index { exit_fade } else { None };
+            for (col_index, cell) in line.iter().enumerate() {
+                let x = self.camera.to_screen_x(col_index as i32);
 
-                // render missiles and check collision with emenies
-                let missiles = &mut self.missiles.lock().unwrap();
-                let mut missile_indexes_to_remove = vec![];
-                for (missile_index, missile) in missiles.iter_mut().enumerate() {
-                    let missile_x = missile.x;
-                    let missile_y = missile.y.lock().unwrap();
-
-                    stdout
-                        .queue(MoveTo(missile_x, *missile_y))?
-                        .queue(SetForegroundColor(Color::Red))?
-                        .queue(SetBackgroundColor(Color::Blue))?
-                        .queue(Print("ðŸ­¯"))?;
-                    stdout.flush()?;
-
-                    if cell.kind == Kind::ENEMY && missile_x == x && *missile_y <= y {
-                        missile_indexes_to_remove.push(missile_index);
-                        cell.kind = Kind::RIVER;
-                    }
-                    if *missile_y == 0 {
-                        missile_indexes_to_remove.push(missile_index);
-                    }
-                }
-                for index in missile_indexes_to_remove.iter() {
-                    missiles.remove(*index);
+                if let Some(colors) = theme.get(&cell.kind) {
+                    let glyph = row_fade.unwrap_or_else(|| cell.kind.glyph());
+                    back_buffer.set(x, y, RenderCell::new(glyph, colors.fg(), colors.bg()));
                 }
             }
         }
 
+        // render the surviving entities (missiles and anything else
+        // implementing GameEntity) on top of the scene
+        let entities = self.entities.lock().unwrap();
+        for entity in entities.iter() {
+            entity.draw(back_buffer, &self.camera);
+        }
+        drop(entities);
+
         // render player
-        let player = &self.player.lock().unwrap();
-        stdout
-            .queue(MoveTo(player.x, player.y))?
-            .queue(SetBackgroundColor(Color::Blue))?
-            .queue(SetForegroundColor(Color::Black))?
-            .queue(Print("ðŸ›¦"))?;
+        let player = self.player.lock().unwrap();
+        back_buffer.set(
+            self.camera.to_screen_x(player.x),
+            player.y,
+            RenderCell::new("ðŸ›¦", Color::Black, Color::Blue),
+        );
+        drop(player);
 
-        stdout.flush()?;
+        // render the netplay peer's plane, if any
+        if self.net.is_some() {
+            let remote_player = self.remote_player.lock().unwrap();
+            back_buffer.set(
+                self.camera.to_screen_x(remote_player.x),
+                remote_player.y,
+                RenderCell::new("ðŸ›¦", Color::Black, Color::Green),
+            );
+        }
+
+        self.present(&mut stdout)?;
 
-        // checking player collision with enemy or land
-        let scene_player_match_cell = {
+        self.score += 1 + outcome.enemies_destroyed * ENEMY_SCORE_VALUE;
+
+        if outcome.collided {
+            let qualifies = self.high_scores.qualifies(self.score);
+            let mut state = self.state.lock().unwrap();
+            *state = if qualifies {
+                State::EnterInitials
+            } else {
+                State::GameOver
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Advances every entity for one frame, then resolves collisions against
+    /// `scene` centrally: destroys the enemies an entity hits, drops the
+    /// entities that died doing it, and reports whether the player is now
+    /// standing on land or an enemy. Shared by the rendered game loop and
+    /// the headless games the trainer plays.
+    /// Ticks every entity and checks for collisions against `scene`. Takes
+    /// the fields it needs individually, rather than `&mut self`, so callers
+    /// can hold a live mutable borrow into `self.scene` (the scene window)
+    /// at the same time — `self.entities`/`self.player`/`self.audio` are
+    /// disjoint fields, but a `&mut self` receiver would hide that from the
+    /// borrow checker.
+    fn resolve_frame(
+        entities: &Mutex<Vec<Box<dyn GameEntity>>>,
+        player: &Mutex<Player>,
+        audio: &audio::AudioPlayer,
+        scene: &mut [&mut Vec<Cell>],
+        game_state: &GameState,
+    ) -> FrameOutcome {
+        let mut enemies_destroyed = 0;
+
+        let mut entities = entities.lock().unwrap();
+        for entity in entities.iter_mut() {
+            entity.tick(game_state);
+
+            let (x, y) = entity.position();
+            let Ok(x) = usize::try_from(x) else {
+                continue;
+            };
+            if let Some(cell) = scene.get_mut(usize::from(y)).and_then(|row| row.get_mut(x)) {
+                if cell.kind == Kind::ENEMY {
+                    cell.kind = Kind::RIVER;
+                    entity.kill();
+                    enemies_destroyed += 1;
+                    audio.play_explosion();
+                }
+            }
+        }
+        entities.retain(|entity| entity.is_alive());
+        drop(entities);
+
+        let player = player.lock().unwrap();
+        let player_x = usize::try_from(player.x).unwrap_or(usize::MAX);
+        let collided = {
             let row = scene.get(usize::from(player.y)).unwrap();
-            row.get(usize::from(player.x)).unwrap()
+            row.get(player_x).is_some_and(|cell| cell.kind == Kind::LAND || cell.kind == Kind::ENEMY)
+        };
+
+        FrameOutcome {
+            collided,
+            enemies_destroyed,
+        }
+    }
+
+    /// Rebuilds both buffers to the new terminal size and forces a full
+    /// redraw on the next `present` call, since a resized terminal needs
+    /// its whole surface repainted anyway.
+    fn resize_buffers(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.term_size = (width, height);
+        self.buffer_a = FrameBuffer::new(width, height);
+        self.buffer_b = FrameBuffer::new(width, height);
+        stdout().queue(Clear(ClearType::All))?;
+        Ok(())
+    }
+
+    /// Diffs the back buffer against the front buffer and emits a
+    /// `MoveTo`/`SetColors`/`Print` sequence only for the cells that
+    /// changed, then flushes once and swaps the buffers.
+    fn present(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+        let width = self.term_size.0;
+        let (front, back) = if self.front_is_a {
+            (&self.buffer_a, &self.buffer_b)
+        } else {
+            (&self.buffer_b, &self.buffer_a)
         };
-        if scene_player_match_cell.kind == Kind::LAND || scene_player_match_cell.kind == Kind::ENEMY
-        {
-            let state_cloned = self.state.clone();
-            let mut state = state_cloned.lock().unwrap();
-            *state = State::GameOver;
+
+        for (index, back_cell) in back.cells.iter().enumerate() {
+            if front.cells.get(index) == Some(back_cell) {
+                continue;
+            }
+            let x = (index % usize::from(width)) as u16;
+            let y = (index / usize::from(width)) as u16;
+            stdout
+                .queue(MoveTo(x, y))?
+                .queue(SetForegroundColor(back_cell.fg))?
+                .queue(SetBackgroundColor(back_cell.bg))?
+                .queue(Print(&back_cell.glyph))?;
         }
+        stdout.flush()?;
+
+        self.front_is_a = !self.front_is_a;
 
         Ok(())
     }
 }
 
+struct FrameOutcome {
+    collided: bool,
+    enemies_destroyed: u32,
+}
+
+/// A headless game's result, scored by the trainer's genetic algorithm.
+pub(crate) struct Fitness {
+    pub frames_survived: u32,
+    pub enemies_destroyed: u32,
+}
+impl Fitness {
+    pub fn score(&self, enemy_kill_weight: f64) -> f64 {
+        f64::from(self.frames_survived) + enemy_kill_weight * f64::from(self.enemies_destroyed)
+    }
+}
+
+/// Plays one full game with `network` driving the player and no rendering,
+/// for the genetic trainer. Ends at game over or after a generous frame cap,
+/// whichever comes first, so a network that never dies can't hang training.
+/// `seed` picks the river: the trainer passes the same seed for every
+/// network in a generation so they're all ranked on the same map.
+pub(crate) fn play_headless(network: &nn::Network, cols: u16, rows: u16, seed: u64) -> Fitness {
+    const MAX_FRAMES: u32 = 3000;
+
+    let mut game = Game::new_headless(cols, rows, seed);
+    let mut frames_survived = 0;
+    let mut enemies_destroyed = 0;
+
+    for _ in 0..MAX_FRAMES {
+        let frame_index = {
+            let mut frame = game.frame.lock().unwrap();
+            *frame += 1;
+            *frame
+        };
+        let row = {
+            let mut scroll = game.scroll.lock().unwrap();
+            *scroll += camera::ONE;
+            camera::row_from_scroll(*scroll)
+        };
+
+        let features = game.extract_features(rows, cols);
+        let action = network.decide(&features);
+        game.apply_action(action);
+
+        let game_state = GameState {
+            frame: frame_index,
+            rows,
+            cols,
+        };
+        let mut scene = game.scene.get_current_scene(row, rows);
+        scene.reverse();
+        let outcome =
+            Game::resolve_frame(&game.entities, &game.player, &game.audio, &mut scene, &game_state);
+
+        frames_survived += 1;
+        enemies_destroyed += outcome.enemies_destroyed;
+
+        if outcome.collided {
+            break;
+        }
+    }
+
+    Fitness {
+        frames_survived,
+        enemies_destroyed,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct RenderCell {
+    glyph: String,
+    fg: Color,
+    bg: Color,
+}
+impl RenderCell {
+    fn new(glyph: &str, fg: Color, bg: Color) -> Self {
+        Self {
+            glyph: glyph.to_string(),
+            fg,
+            bg,
+        }
+    }
+}
+impl Default for RenderCell {
+    fn default() -> Self {
+        Self {
+            glyph: " ".to_string(),
+            fg: Color::Reset,
+            bg: Color::Reset,
+        }
+    }
+}
+
+struct FrameBuffer {
+    width: u16,
+    height: u16,
+    cells: Vec<RenderCell>,
+}
+impl FrameBuffer {
+    fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![RenderCell::default(); usize::from(width) * usize::from(height)],
+        }
+    }
+
+    /// Accepts a signed screen column so an entity shifted off the left
+    /// edge by the camera's centering offset is simply dropped instead of
+    /// wrapping or panicking.
+    fn set(&mut self, x: i32, y: u16, cell: RenderCell) {
+        if x < 0 || y >= self.height {
+            return;
+        }
+        let x = x as u32;
+        if x >= u32::from(self.width) {
+            return;
+        }
+        let index = usize::from(y) * usize::from(self.width) + x as usize;
+        self.cells[index] = cell;
+    }
+
+    fn clear(&mut self) {
+        self.cells.fill(RenderCell::default());
+    }
+}
+
 struct Scene {
     cells: Vec<Vec<Cell>>,
+    theme: HashMap<Kind, level::ThemeColors>,
+    /// The level's display name, shown on the main screen.
+    name: String,
+    /// The width the level's river bands were designed at, read from the
+    /// level file rather than the local terminal so two differently-sized
+    /// terminals seeded the same way still generate identical rivers;
+    /// `Camera` compares this against the current terminal width to decide
+    /// whether the playfield needs to be recentered.
+    designed_width: u16,
 }
 impl Scene {
-    fn make() -> Self {
+    /// Builds the scene from `level_path`, drawing every random spawn from
+    /// `rng` so two sides of a netplay session seeded with the same value
+    /// generate byte-for-byte identical rivers.
+    fn make(level_path: &str, rng: &mut impl Rng) -> Self {
         let mut result: Vec<Vec<Cell>> = vec![];
-        let (terminal_width, _) = size().expect("Failed to get terminal size.");
-        let designs: Vec<Vec<f64>> = fs::read_to_string("scene.design")
-            .expect("Failed to read design file.")
-            .lines()
-            .into_iter()
-            .map(|line| {
-                return line
-                    .split(' ')
-                    .into_iter()
-                    .map(|item| item.parse::<f64>().unwrap())
-                    .collect();
-            })
-            .collect();
+        let level = level::LevelConfig::load(level_path);
+        let designed_width = level.width;
 
-        for design_index in 0..designs.len() {
-            let design = designs.get(design_index).unwrap();
-            let (mut line, part_height) = Self::generate_line(design, terminal_width);
+        for (segment_index, segment) in level.segments.iter().enumerate() {
+            let mut line = Self::generate_line(segment, designed_width);
 
-            // generate first scene of the game without enemies
-            let mut has_enemy = true;
-            if design_index == 0 {
-                has_enemy = false;
-            }
-            let mut part = Self::generate_with_height(&mut line, part_height, has_enemy);
+            // generate first scene of the game without spawns
+            let spawns: &[level::SpawnRule] = if segment_index == 0 {
+                &[]
+            } else {
+                &segment.spawns
+            };
+            let mut part = Self::generate_with_height(&mut line, segment.height, spawns, rng);
             result.append(&mut part);
         }
 
-        Self { cells: result }
+        Self {
+            cells: result,
+            theme: level.theme,
+            name: level.name,
+            designed_width,
+        }
+    }
+
+    /// The RIVER column closest to the middle of the designed width, for
+    /// spawning the player on water regardless of the local terminal's
+    /// size. Segment 0 (the intro segment every level starts with) sets
+    /// the banding for `cells[0]`, so that row's the one to search.
+    fn spawn_x(&self) -> i32 {
+        let center = usize::from(self.designed_width / 2);
+        let first_row = &self.cells[0];
+        first_row
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.kind == Kind::RIVER)
+            .min_by_key(|(index, _)| index.abs_diff(center))
+            .map_or(center as i32, |(index, _)| index as i32)
     }
 
-    fn percent_to_terminal_size(percent: &f64, terminal_width: u16) -> usize {
+    fn percent_to_terminal_size(percent: f64, terminal_width: u16) -> usize {
         f64::floor(percent * f64::from(terminal_width) / 100.0) as usize
     }
 
-    fn generate_line(design: &Vec<f64>, terminal_width: u16) -> (Vec<Cell>, usize) {
-        let land_part_one_size =
-            Self::percent_to_terminal_size(design.get(0).unwrap(), terminal_width);
-        let river_part_one_size =
-            Self::percent_to_terminal_size(design.get(1).unwrap(), terminal_width);
-        let land_part_two_size =
-            Self::percent_to_terminal_size(design.get(2).unwrap(), terminal_width);
-        let river_part_two_size =
-            Self::percent_to_terminal_size(design.get(3).unwrap(), terminal_width);
-        let mut land_part_three_size =
-            Self::percent_to_terminal_size(design.get(4).unwrap(), terminal_width);
+    fn generate_line(segment: &level::SegmentTemplate, terminal_width: u16) -> Vec<Cell> {
+        let [land_one, river_one, land_two, river_two, land_three] = segment.band_percentages();
+
+        let land_part_one_size = Self::percent_to_terminal_size(land_one, terminal_width);
+        let river_part_one_size = Self::percent_to_terminal_size(river_one, terminal_width);
+        let land_part_two_size = Self::percent_to_terminal_size(land_two, terminal_width);
+        let river_part_two_size = Self::percent_to_terminal_size(river_two, terminal_width);
+        let mut land_part_three_size = Self::percent_to_terminal_size(land_three, terminal_width);
         let total_size = land_part_one_size
             + river_part_one_size
             + land_part_two_size
@@ -441,7 +1064,6 @@ impl Scene {
         if total_size < usize::from(terminal_width) {
             land_part_three_size = land_part_three_size + usize::from(terminal_width) - total_size;
         }
-        let part_height = *design.get(5).unwrap() as usize;
 
         let mut v = vec![];
         let mut land_part_one = Cell::create_cells_vec(land_part_one_size, Kind::LAND);
@@ -456,45 +1078,40 @@ impl Scene {
         v.append(&mut river_part_two);
         v.append(&mut land_part_three);
 
-        (v, part_height)
+        v
     }
 
     fn generate_with_height(
-        line: &mut Vec<Cell>,
+        line: &mut [Cell],
         height: usize,
-        with_enemy: bool,
+        spawns: &[level::SpawnRule],
+        rng: &mut impl Rng,
     ) -> Vec<Vec<Cell>> {
         let mut result: Vec<Vec<Cell>> = vec![];
-        if with_enemy {
-            let river_indexes: Vec<usize> = line
-                .iter()
-                .enumerate()
-                .filter_map(|(index, cell)| {
-                    if cell.kind == Kind::RIVER {
-                        return Some(index);
-                    } else {
-                        return None;
-                    }
-                })
-                .collect();
+        if spawns.is_empty() {
             for _ in 0..height {
-                let mut line = line.clone();
-                let mut rng = thread_rng();
-                let enemy_posibility = rng.gen_bool(1.0 / 2.0);
-                if enemy_posibility {
-                    let enemy_index = river_indexes
-                        .get(rng.gen_range(0..river_indexes.len()))
-                        .unwrap()
-                        .clone();
-                    let cell = line.get_mut(enemy_index).unwrap();
-                    *cell = Cell { kind: Kind::ENEMY };
-                }
-                result.push(line.clone());
+                result.push(line.to_owned());
             }
-        } else {
-            for _ in 0..height {
-                result.push(line.clone());
+            return result;
+        }
+
+        let river_indexes: Vec<usize> = line
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| (cell.kind == Kind::RIVER).then_some(index))
+            .collect();
+
+        for _ in 0..height {
+            let mut line = line.to_owned();
+            for spawn in spawns {
+                if river_indexes.is_empty() || !rng.gen_bool(spawn.probability) {
+                    continue;
+                }
+                let spawn_index = *river_indexes.get(rng.gen_range(0..river_indexes.len())).unwrap();
+                let cell = line.get_mut(spawn_index).unwrap();
+                *cell = Cell { kind: spawn.kind };
             }
+            result.push(line.clone());
         }
         result
     }
@@ -530,11 +1147,27 @@ impl Scene {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+// Variant names are upper-case to match the `kind = "ENEMY"`-style TOML
+// values level files already ship (see levels/classic.toml); renaming them
+// would mean adding serde rename attributes for no behavior change.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 enum Kind {
     LAND,
     RIVER,
     ENEMY,
+    BRIDGE,
+    FUEL,
+}
+impl Kind {
+    fn glyph(&self) -> &'static str {
+        match self {
+            Kind::LAND | Kind::RIVER => "â–ˆ",
+            Kind::ENEMY => "âœˆ",
+            Kind::BRIDGE => "=",
+            Kind::FUEL => "+",
+        }
+    }
 }
 #[derive(Clone, Copy)]
 struct Cell {
@@ -543,6 +1176,6 @@ struct Cell {
 impl Cell {
     fn create_cells_vec(size: usize, kind: Kind) -> Vec<Cell> {
         let cell = Cell { kind };
-        vec![cell; size as usize]
+        vec![cell; size]
     }
 }
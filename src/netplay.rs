@@ -0,0 +1,142 @@
+use laminar::{Packet, Socket, SocketEvent};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::nn::Action;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Serialize, Deserialize)]
+enum Message {
+    /// Sent by the host once it has picked a seed, so both ends start
+    /// `Scene::make` from the same RNG state.
+    Handshake { seed: u64 },
+    Input { frame: u64, action: Action },
+}
+
+/// A two-player lockstep session over a reliable-ordered UDP channel. A
+/// background thread owns the `laminar` socket and shuttles messages
+/// through plain `Mutex`-guarded state, the same way `Game` hands frame
+/// counting and input off to background threads.
+pub(crate) struct NetplaySession {
+    pub seed: u64,
+    remote_frame: Arc<Mutex<u64>>,
+    incoming: Arc<Mutex<VecDeque<(u64, Action)>>>,
+    outgoing: Arc<Mutex<VecDeque<Vec<u8>>>>,
+}
+impl NetplaySession {
+    /// Binds `port` and waits for a peer to connect, then generates the
+    /// shared scene seed and sends it across.
+    pub fn host(port: u16) -> Self {
+        let mut socket = Socket::bind(("0.0.0.0", port)).expect("Failed to bind netplay socket.");
+        let sender = socket.get_packet_sender();
+        let receiver = socket.get_event_receiver();
+
+        let peer = loop {
+            socket.manual_poll(Instant::now());
+            if let Ok(SocketEvent::Packet(packet)) = receiver.try_recv() {
+                break packet.addr();
+            }
+        };
+
+        let seed = thread_rng().gen();
+        let handshake = bincode::serialize(&Message::Handshake { seed })
+            .expect("Failed to serialize netplay handshake.");
+        sender
+            .send(Packet::reliable_ordered(peer, handshake, Some(0)))
+            .expect("Failed to send netplay handshake.");
+        socket.manual_poll(Instant::now());
+
+        Self::spawn(socket, peer, seed)
+    }
+
+    /// Connects to a host already listening at `addr` and waits for the
+    /// seed it picks.
+    pub fn join(addr: &str) -> Self {
+        let peer: SocketAddr = addr.parse().expect("Invalid --join address.");
+        let mut socket = Socket::bind_any().expect("Failed to bind netplay socket.");
+        let sender = socket.get_packet_sender();
+        let receiver = socket.get_event_receiver();
+
+        sender
+            .send(Packet::reliable_ordered(peer, vec![0], Some(0)))
+            .expect("Failed to send netplay hello.");
+
+        let seed = loop {
+            socket.manual_poll(Instant::now());
+            if let Ok(SocketEvent::Packet(packet)) = receiver.try_recv() {
+                if let Ok(Message::Handshake { seed }) = bincode::deserialize(packet.payload()) {
+                    break seed;
+                }
+            }
+        };
+
+        Self::spawn(socket, peer, seed)
+    }
+
+    fn spawn(mut socket: Socket, peer: SocketAddr, seed: u64) -> Self {
+        let remote_frame = Arc::new(Mutex::new(0));
+        let incoming: Arc<Mutex<VecDeque<(u64, Action)>>> = Arc::new(Mutex::new(VecDeque::new()));
+        let outgoing: Arc<Mutex<VecDeque<Vec<u8>>>> = Arc::new(Mutex::new(VecDeque::new()));
+
+        let remote_frame_clone = remote_frame.clone();
+        let incoming_clone = incoming.clone();
+        let outgoing_clone = outgoing.clone();
+
+        thread::spawn(move || {
+            let sender = socket.get_packet_sender();
+            let receiver = socket.get_event_receiver();
+            loop {
+                while let Some(payload) = outgoing_clone.lock().unwrap().pop_front() {
+                    let _ = sender.send(Packet::reliable_ordered(peer, payload, Some(0)));
+                }
+                socket.manual_poll(Instant::now());
+                while let Ok(SocketEvent::Packet(packet)) = receiver.try_recv() {
+                    if let Ok(Message::Input { frame, action }) = bincode::deserialize(packet.payload())
+                    {
+                        *remote_frame_clone.lock().unwrap() = frame;
+                        incoming_clone.lock().unwrap().push_back((frame, action));
+                    }
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+
+        Self {
+            seed,
+            remote_frame,
+            incoming,
+            outgoing,
+        }
+    }
+
+    /// Queues this frame's local action to go out to the peer. Queued
+    /// packets are sent in order over a reliable-ordered channel and never
+    /// overwritten, so a quiet frame (Idle) can't cause a later frame's
+    /// input to be dropped before the background thread drains it.
+    pub fn send_local(&self, frame: u64, action: Action) {
+        let payload =
+            bincode::serialize(&Message::Input { frame, action }).expect("Failed to serialize input.");
+        self.outgoing.lock().unwrap().push_back(payload);
+    }
+
+    /// A clone of the shared `remote_frame` handle, for the frame-counter
+    /// thread to poll directly instead of borrowing the whole session.
+    pub fn remote_frame_handle(&self) -> Arc<Mutex<u64>> {
+        self.remote_frame.clone()
+    }
+
+    /// Pops the oldest not-yet-applied `(frame, action)` the peer sent.
+    /// Delivery order matches send order (reliable-ordered), so draining
+    /// this queue front-to-back applies every one of the peer's inputs
+    /// exactly once, instead of a single latest-value slot that can skip
+    /// over inputs the poller hasn't caught up to yet.
+    pub fn take_remote_input(&self) -> Option<(u64, Action)> {
+        self.incoming.lock().unwrap().pop_front()
+    }
+}
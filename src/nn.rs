@@ -0,0 +1,145 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+
+pub const INPUT_SIZE: usize = 8;
+pub const HIDDEN_SIZE: usize = 8;
+pub const OUTPUT_SIZE: usize = 3;
+
+const FIRE_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    Idle,
+    MoveLeft,
+    MoveRight,
+    Fire,
+}
+
+/// A small feedforward network used by the autopilot: `INPUT_SIZE` inputs,
+/// one `tanh` hidden layer of `HIDDEN_SIZE` units, and `OUTPUT_SIZE` outputs
+/// read as move-left / move-right / fire scores.
+#[derive(Clone)]
+pub struct Network {
+    w1: Vec<f64>, // HIDDEN_SIZE x INPUT_SIZE
+    b1: Vec<f64>, // HIDDEN_SIZE
+    w2: Vec<f64>, // OUTPUT_SIZE x HIDDEN_SIZE
+    b2: Vec<f64>, // OUTPUT_SIZE
+}
+impl Network {
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            w1: (0..HIDDEN_SIZE * INPUT_SIZE)
+                .map(|_| rng.gen_range(-1.0..1.0))
+                .collect(),
+            b1: (0..HIDDEN_SIZE).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+            w2: (0..OUTPUT_SIZE * HIDDEN_SIZE)
+                .map(|_| rng.gen_range(-1.0..1.0))
+                .collect(),
+            b2: (0..OUTPUT_SIZE).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+        }
+    }
+
+    pub fn feedforward(&self, inputs: &[f64; INPUT_SIZE]) -> [f64; OUTPUT_SIZE] {
+        let mut hidden = [0.0; HIDDEN_SIZE];
+        for (h, hidden_value) in hidden.iter_mut().enumerate() {
+            let mut sum = self.b1[h];
+            for (i, input) in inputs.iter().enumerate() {
+                sum += self.w1[h * INPUT_SIZE + i] * input;
+            }
+            *hidden_value = sum.tanh();
+        }
+
+        let mut outputs = [0.0; OUTPUT_SIZE];
+        for (o, output_value) in outputs.iter_mut().enumerate() {
+            let mut sum = self.b2[o];
+            for (h, hidden_value) in hidden.iter().enumerate() {
+                sum += self.w2[o * HIDDEN_SIZE + h] * hidden_value;
+            }
+            *output_value = sum.tanh();
+        }
+        outputs
+    }
+
+    /// Reads the player's next move off the network: the argmax of the
+    /// three outputs picks move-left/move-right/fire, except fire also has
+    /// to clear `FIRE_THRESHOLD` since it's the rarer, higher-stakes action.
+    pub fn decide(&self, inputs: &[f64; INPUT_SIZE]) -> Action {
+        let outputs = self.feedforward(inputs);
+        let (best_index, &best_value) = outputs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .expect("Outputs is never empty.");
+
+        match best_index {
+            0 => Action::MoveLeft,
+            1 => Action::MoveRight,
+            2 if best_value > FIRE_THRESHOLD => Action::Fire,
+            _ => Action::Idle,
+        }
+    }
+
+    pub fn mutate(&mut self, rng: &mut impl Rng, rate: f64) {
+        for weight in self
+            .w1
+            .iter_mut()
+            .chain(self.b1.iter_mut())
+            .chain(self.w2.iter_mut())
+            .chain(self.b2.iter_mut())
+        {
+            *weight += rng.gen_range(-rate..rate);
+        }
+    }
+
+    /// Single-point crossover: each weight vector is spliced at an
+    /// independent random point between the two parents.
+    pub fn crossover(a: &Network, b: &Network, rng: &mut impl Rng) -> Network {
+        Self {
+            w1: splice(&a.w1, &b.w1, rng),
+            b1: splice(&a.b1, &b.b1, rng),
+            w2: splice(&a.w2, &b.w2, rng),
+            b2: splice(&a.b2, &b.b2, rng),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let lines: Vec<String> = self
+            .w1
+            .iter()
+            .chain(&self.b1)
+            .chain(&self.w2)
+            .chain(&self.b2)
+            .map(|weight| weight.to_string())
+            .collect();
+        fs::write(path, lines.join("\n"))
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut values = contents
+            .lines()
+            .map(|line| line.parse::<f64>().expect("Failed to parse autopilot weight."));
+
+        let w1 = (0..HIDDEN_SIZE * INPUT_SIZE)
+            .map(|_| values.next().expect("autopilot.weights is truncated."))
+            .collect();
+        let b1 = (0..HIDDEN_SIZE)
+            .map(|_| values.next().expect("autopilot.weights is truncated."))
+            .collect();
+        let w2 = (0..OUTPUT_SIZE * HIDDEN_SIZE)
+            .map(|_| values.next().expect("autopilot.weights is truncated."))
+            .collect();
+        let b2 = (0..OUTPUT_SIZE)
+            .map(|_| values.next().expect("autopilot.weights is truncated."))
+            .collect();
+
+        Ok(Self { w1, b1, w2, b2 })
+    }
+}
+
+fn splice(a: &[f64], b: &[f64], rng: &mut impl Rng) -> Vec<f64> {
+    let point = rng.gen_range(0..=a.len());
+    a[..point].iter().chain(&b[point..]).copied().collect()
+}
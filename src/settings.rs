@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Player-editable preferences, persisted as TOML next to the binary so a
+/// run remembers the last volume, autopilot toggle, and chosen level.
+/// Missing or unparsable on first launch, so `load` falls back to
+/// `Settings::default()` instead of the `LevelConfig::load`-style panic.
+#[derive(Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+    #[serde(default)]
+    pub autopilot: bool,
+    #[serde(default = "default_level")]
+    pub level: String,
+    #[serde(default)]
+    pub soundtrack: Soundtrack,
+}
+impl Settings {
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) {
+        let contents = toml::to_string_pretty(self).expect("Failed to serialize settings.");
+        let _ = fs::write(path, contents);
+    }
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: default_volume(),
+            autopilot: false,
+            level: default_level(),
+            soundtrack: Soundtrack::default(),
+        }
+    }
+}
+
+fn default_volume() -> f32 {
+    0.8
+}
+
+fn default_level() -> String {
+    crate::LEVEL_CONFIG_PATH.to_string()
+}
+
+/// File paths for the audio layer's tracks, swappable without recompiling.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Soundtrack {
+    #[serde(default = "default_background_track")]
+    pub background: String,
+    #[serde(default = "default_fire_effect")]
+    pub fire: String,
+    #[serde(default = "default_explosion_effect")]
+    pub explosion: String,
+}
+impl Default for Soundtrack {
+    fn default() -> Self {
+        Self {
+            background: default_background_track(),
+            fire: default_fire_effect(),
+            explosion: default_explosion_effect(),
+        }
+    }
+}
+
+fn default_background_track() -> String {
+    "audio/background.ogg".to_string()
+}
+
+fn default_fire_effect() -> String {
+    "audio/fire.ogg".to_string()
+}
+
+fn default_explosion_effect() -> String {
+    "audio/explosion.ogg".to_string()
+}
@@ -0,0 +1,72 @@
+use crate::nn::Network;
+use crossterm::terminal::size;
+use rand::prelude::*;
+
+const POPULATION_SIZE: usize = 30;
+const ELITE_COUNT: usize = 5;
+const MUTATION_RATE: f64 = 0.2;
+const ENEMY_KILL_WEIGHT: f64 = 50.0;
+
+/// Runs `generations` rounds of a genetic algorithm: each network in the
+/// population plays a full headless game, the top performers survive
+/// unchanged, and the rest of the next generation is bred from them via
+/// crossover plus Gaussian-ish mutation. The best network seen so far is
+/// persisted to `crate::AUTOPILOT_WEIGHTS_PATH` after every generation.
+pub fn train(generations: usize) {
+    let (cols, rows) = size().expect("Failed to get terminal size.");
+    let mut rng = thread_rng();
+    let mut population: Vec<Network> = (0..POPULATION_SIZE)
+        .map(|_| Network::random(&mut rng))
+        .collect();
+
+    let mut best_score = f64::MIN;
+
+    for generation in 0..generations {
+        // Same seed for every network this generation, so they're all
+        // scored on the same river and fitness is comparable within the
+        // population; the seed still varies across generations.
+        let generation_seed: u64 = rng.gen();
+        let mut scored: Vec<(f64, Network)> = population
+            .into_iter()
+            .map(|network| {
+                let fitness = crate::play_headless(&network, cols, rows, generation_seed);
+                (fitness.score(ENEMY_KILL_WEIGHT), network)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+        if scored[0].0 > best_score {
+            best_score = scored[0].0;
+            scored[0]
+                .1
+                .save(crate::AUTOPILOT_WEIGHTS_PATH)
+                .expect("Failed to save autopilot weights.");
+        }
+
+        println!(
+            "generation {generation}: best score = {:.1} (all-time best = {:.1})",
+            scored[0].0, best_score
+        );
+
+        let elites: Vec<Network> = scored
+            .into_iter()
+            .take(ELITE_COUNT)
+            .map(|(_, network)| network)
+            .collect();
+
+        population = elites.clone();
+        while population.len() < POPULATION_SIZE {
+            let parent_a = elites.choose(&mut rng).expect("Elites is never empty.");
+            let parent_b = elites.choose(&mut rng).expect("Elites is never empty.");
+            let mut child = Network::crossover(parent_a, parent_b, &mut rng);
+            child.mutate(&mut rng, MUTATION_RATE);
+            population.push(child);
+        }
+    }
+
+    println!(
+        "Training complete. Best score = {:.1}. Weights saved to {}.",
+        best_score,
+        crate::AUTOPILOT_WEIGHTS_PATH
+    );
+}